@@ -0,0 +1,56 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use serde::Deserialize;
+
+use crate::signer::SignerBackend;
+
+/// Command-line entry point for the keeper. A single TOML file describes every chain the process
+/// should maintain the beacon oracle for.
+#[derive(Parser, Debug)]
+#[command(author, version, about = "EigenLayer beacon oracle keeper")]
+pub struct Cli {
+    /// Path to the TOML configuration file.
+    #[arg(short, long, default_value = "keeper.toml")]
+    pub config: PathBuf,
+}
+
+/// Top-level keeper configuration: a list of chains, each maintained by its own async task.
+#[derive(Deserialize, Debug, Clone)]
+pub struct KeeperConfig {
+    pub chains: Vec<ChainConfig>,
+}
+
+/// Per-chain configuration. Each chain has its own RPC endpoints, contract, cadence, and signer.
+#[derive(Deserialize, Debug, Clone)]
+pub struct ChainConfig {
+    /// Human-readable label used in logs and metric labels (e.g. `mainnet`, `holesky`).
+    pub name: String,
+    /// Execution-layer JSON-RPC endpoint.
+    pub rpc_url: String,
+    /// Beacon-node HTTP API endpoint used for consensus-layer verification.
+    pub beacon_rpc_url: String,
+    /// Address of the `EigenlayerBeaconOracle` contract on this chain.
+    pub contract_address: String,
+    /// Number of execution blocks between stored checkpoints.
+    ///
+    /// The checkpoint cadence is denominated in blocks, not slots: the contract is keyed by
+    /// execution-block timestamp, so checkpoints are enumerated by block number. The slot clock
+    /// governs *when* a tick wakes (on slot boundaries), not the interval unit.
+    pub block_interval: u64,
+    /// Key-management backend used to sign this chain's transactions.
+    pub signer: SignerBackend,
+}
+
+impl KeeperConfig {
+    /// Loads and parses the keeper configuration from `path`.
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read config file {}", path.display()))?;
+        let config: KeeperConfig =
+            toml::from_str(&contents).context("failed to parse keeper config")?;
+        anyhow::ensure!(!config.chains.is_empty(), "config must list at least one chain");
+        Ok(config)
+    }
+}
@@ -0,0 +1,83 @@
+use anyhow::Result;
+use beacon_api_client::{mainnet::MainnetClientTypes, BlockId, Client};
+use ethers::types::{H256, U256};
+use url::Url;
+
+use crate::slot_clock::{SlotClock, SECONDS_PER_SLOT};
+
+/// Reads `SECONDS_PER_SLOT` from the beacon node's `/eth/v1/config/spec` endpoint. The spec
+/// returns every value as a decimal string, so we parse it out of the returned map.
+async fn fetch_seconds_per_slot(client: &Client<MainnetClientTypes>) -> Result<u64> {
+    let spec: std::collections::HashMap<String, String> = client.get_spec().await?;
+    let value = spec
+        .get("SECONDS_PER_SLOT")
+        .ok_or_else(|| anyhow::anyhow!("beacon spec is missing SECONDS_PER_SLOT"))?;
+    Ok(value.parse()?)
+}
+
+/// Thin wrapper around a beacon-node HTTP client used to cross-check, against the consensus
+/// layer, the beacon block root that the execution layer's EIP-4788 ring buffer will expose
+/// for a given timestamp before the oracle commits to it.
+pub struct ConsensusLayer {
+    client: Client<MainnetClientTypes>,
+    slot_clock: SlotClock,
+}
+
+impl ConsensusLayer {
+    /// Connects to the beacon API at `beacon_rpc_url` and builds a [`SlotClock`] from the chain's
+    /// genesis time and `SECONDS_PER_SLOT` — both read from the node so the slot math is correct
+    /// on testnets and L2s where these differ from Ethereum L1's defaults.
+    pub async fn new(beacon_rpc_url: &str) -> Result<Self> {
+        let client = Client::new(Url::parse(beacon_rpc_url)?);
+        let genesis = client.get_genesis_details().await?;
+        let seconds_per_slot = fetch_seconds_per_slot(&client).await.unwrap_or_else(|err| {
+            tracing::warn!(error = %err, default = SECONDS_PER_SLOT, "could not read SECONDS_PER_SLOT from beacon spec, using default");
+            SECONDS_PER_SLOT
+        });
+        Ok(Self {
+            client,
+            slot_clock: SlotClock::new(genesis.genesis_time, seconds_per_slot),
+        })
+    }
+
+    /// The slot clock anchored to this chain's genesis, shared with the scheduler.
+    pub fn slot_clock(&self) -> SlotClock {
+        self.slot_clock
+    }
+
+    /// Fetches the parent beacon block root that EIP-4788 exposes for `timestamp`.
+    ///
+    /// Under EIP-4788 `BEACON_ROOTS[timestamp]` is the *parent* beacon root — the root of the
+    /// most recent beacon block preceding the one proposed in the slot covering `timestamp`.
+    /// That is exactly the `parent_root` of the beacon block in that slot, so we read it from
+    /// the slot's header rather than the slot's own root. Using `parent_root` also means a
+    /// skipped previous slot is handled for free (it points at the prior non-skipped block).
+    ///
+    /// Returns `Ok(None)` when the slot itself was skipped or the header is otherwise missing,
+    /// which callers treat as "the 4788 root for this timestamp is absent, do not write it".
+    pub async fn parent_beacon_block_root(&self, timestamp: U256) -> Result<Option<H256>> {
+        let slot = self.slot_clock.slot_of_block_timestamp(timestamp);
+        match self.client.get_beacon_header(BlockId::Slot(slot)).await {
+            Ok(header) => Ok(Some(H256::from_slice(
+                header.header.message.parent_root.as_ref(),
+            ))),
+            // A missing header means the slot was skipped, so no execution block (and no 4788
+            // root) exists for this timestamp.
+            Err(beacon_api_client::Error::Api(err)) if err.code == 404 => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Confirms that `expected_root` (the parent beacon root the contract will store for
+    /// `timestamp`) matches the consensus layer's canonical parent root for the slot covering
+    /// that timestamp.
+    ///
+    /// Returns `false` when the slot is missing or the roots disagree, in which case the keeper
+    /// skips the write rather than persisting a timestamp whose 4788 root is absent or wrong.
+    pub async fn verify_block_root(&self, timestamp: U256, expected_root: H256) -> Result<bool> {
+        match self.parent_beacon_block_root(timestamp).await? {
+            Some(root) => Ok(root == expected_root),
+            None => Ok(false),
+        }
+    }
+}
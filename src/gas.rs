@@ -0,0 +1,178 @@
+use std::{sync::Arc, time::Duration};
+
+use anyhow::Result;
+use ethers::{
+    middleware::Middleware,
+    types::{
+        transaction::eip2718::TypedTransaction, Address, BlockNumber, Eip1559TransactionRequest,
+        TransactionReceipt, U256,
+    },
+};
+
+// Per-block priority-fee percentile requested from `eth_feeHistory`; the suggested tip is the
+// mean of these per-block values across the sampled window.
+const REWARD_PERCENTILE: f64 = 50.0;
+// Number of recent blocks sampled from `eth_feeHistory`.
+const FEE_HISTORY_BLOCKS: u64 = 20;
+// Fee bump applied on each resubmission, as the ratio 1125/1000 (12.5%, the minimum the mempool
+// accepts for a same-nonce replacement).
+const BUMP_NUMERATOR: u64 = 1125;
+const BUMP_DENOMINATOR: u64 = 1000;
+// How often to poll for a receipt while waiting out the resubmission window.
+const RECEIPT_POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Gas-management parameters for [`send_add_timestamp`].
+#[derive(Clone, Copy)]
+pub struct GasConfig {
+    /// How many blocks to wait for a receipt before resubmitting with a bumped fee.
+    pub resubmit_after_blocks: u64,
+    /// Maximum number of resubmissions before giving up on this send.
+    pub max_resubmissions: u64,
+}
+
+impl Default for GasConfig {
+    fn default() -> Self {
+        Self {
+            resubmit_after_blocks: 6,
+            max_resubmissions: 5,
+        }
+    }
+}
+
+/// Suggests EIP-1559 fees from recent history: `max_priority_fee_per_gas` is the mean of recent
+/// blocks' [`REWARD_PERCENTILE`]th-percentile tips, and `max_fee_per_gas` leaves headroom for the
+/// base fee to double over the next few blocks, following the standard wallet heuristic.
+pub async fn suggest_fees<M: Middleware>(client: &M) -> Result<(U256, U256)>
+where
+    M::Error: 'static,
+{
+    let history = client
+        .fee_history(
+            FEE_HISTORY_BLOCKS,
+            BlockNumber::Latest,
+            &[REWARD_PERCENTILE],
+        )
+        .await
+        .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+    let tips: Vec<U256> = history.reward.iter().filter_map(|r| r.first().copied()).collect();
+    let max_priority_fee = if tips.is_empty() {
+        U256::zero()
+    } else {
+        tips.iter().fold(U256::zero(), |acc, t| acc + t) / U256::from(tips.len())
+    };
+
+    let base_fee = history
+        .base_fee_per_gas
+        .last()
+        .copied()
+        .unwrap_or_default();
+    // base_fee can at most increase 12.5% per block; double it to stay priced-in for a while.
+    let max_fee = base_fee * 2 + max_priority_fee;
+
+    Ok((max_priority_fee, max_fee))
+}
+
+/// Bumps a fee by [`BUMP_NUMERATOR`]/[`BUMP_DENOMINATOR`] for a same-nonce replacement.
+fn bump(fee: U256) -> U256 {
+    fee * BUMP_NUMERATOR / BUMP_DENOMINATOR
+}
+
+/// Submits an `add_timestamp(timestamp)` call as an EIP-1559 transaction and, if no receipt
+/// appears within [`GasConfig::resubmit_after_blocks`] blocks, resubmits with the same nonce and
+/// a bumped fee up to [`GasConfig::max_resubmissions`] times. This keeps the oracle from hanging
+/// on an underpriced, permanently-pending transaction during a fee spike.
+///
+/// `nonce` is supplied by the caller — it comes from the stacked nonce manager so that
+/// concurrent/backfilled sends receive sequential nonces, while resubmissions of *this* send all
+/// reuse the same nonce and therefore replace rather than duplicate the pending transaction.
+pub async fn send_add_timestamp<M: Middleware + 'static>(
+    client: Arc<M>,
+    contract_address: Address,
+    timestamp: U256,
+    nonce: U256,
+    config: GasConfig,
+) -> Result<Option<TransactionReceipt>>
+where
+    M::Error: 'static,
+{
+    // ABI-encode the `add_timestamp(uint256)` call.
+    let selector = ethers::utils::id("addTimestamp(uint256)")[..4].to_vec();
+    let mut data = selector;
+    let mut encoded = [0u8; 32];
+    timestamp.to_big_endian(&mut encoded);
+    data.extend_from_slice(&encoded);
+
+    let from = client.default_sender().ok_or_else(|| anyhow::anyhow!("signer has no address"))?;
+    let (mut max_priority_fee, mut max_fee) = suggest_fees(client.as_ref()).await?;
+
+    for attempt in 0..=config.max_resubmissions {
+        let tx = Eip1559TransactionRequest::new()
+            .to(contract_address)
+            .from(from)
+            .nonce(nonce)
+            .data(data.clone())
+            .max_priority_fee_per_gas(max_priority_fee)
+            .max_fee_per_gas(max_fee);
+        let tx: TypedTransaction = tx.into();
+
+        let pending = client
+            .send_transaction(tx, None)
+            .await
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+        let tx_hash = *pending;
+        tracing::info!(attempt, ?max_fee, ?max_priority_fee, "submitted add_timestamp tx");
+
+        // Wait up to `resubmit_after_blocks` blocks for a receipt before giving up on this fee.
+        if let Some(receipt) =
+            wait_for_receipt(client.as_ref(), tx_hash, config.resubmit_after_blocks).await?
+        {
+            return Ok(Some(receipt));
+        }
+
+        if attempt == config.max_resubmissions {
+            break;
+        }
+
+        // Still pending after the block window: bump the fee and replace the same nonce.
+        max_priority_fee = bump(max_priority_fee);
+        max_fee = bump(max_fee);
+        tracing::warn!(attempt, "add_timestamp tx still pending, resubmitting with bumped fee");
+    }
+
+    Ok(None)
+}
+
+/// Polls for the receipt of `tx_hash` until it lands or the chain advances `max_blocks` blocks.
+async fn wait_for_receipt<M: Middleware>(
+    client: &M,
+    tx_hash: ethers::types::H256,
+    max_blocks: u64,
+) -> Result<Option<TransactionReceipt>>
+where
+    M::Error: 'static,
+{
+    let start_block = client
+        .get_block_number()
+        .await
+        .map_err(|e| anyhow::anyhow!(e.to_string()))?
+        .as_u64();
+    loop {
+        if let Some(receipt) = client
+            .get_transaction_receipt(tx_hash)
+            .await
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?
+        {
+            return Ok(Some(receipt));
+        }
+        let current_block = client
+            .get_block_number()
+            .await
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?
+            .as_u64();
+        if current_block.saturating_sub(start_block) >= max_blocks {
+            return Ok(None);
+        }
+        tokio::time::sleep(RECEIPT_POLL_INTERVAL).await;
+    }
+}
@@ -1,13 +1,27 @@
 use anyhow::Result;
 use ethers::{
     contract::abigen,
-    middleware::SignerMiddleware,
+    middleware::{NonceManagerMiddleware, SignerMiddleware},
     providers::{Http, Middleware, Provider},
-    types::{Address, Filter, TransactionReceipt, U64},
+    signers::Signer,
+    types::{Address, BlockNumber, Filter, TransactionReceipt, U64},
     utils::hex,
 };
-use ethers_aws::aws_signer::AWSSigner;
-use std::{env, str::FromStr, sync::Arc};
+use std::{str::FromStr, sync::Arc};
+
+use clap::Parser;
+
+mod config;
+mod consensus;
+mod gas;
+mod signer;
+mod slot_clock;
+mod telemetry;
+use config::{ChainConfig, Cli, KeeperConfig};
+use consensus::ConsensusLayer;
+use gas::{send_add_timestamp, GasConfig};
+use signer::{build_signer, KeeperSigner};
+use telemetry::{record_block_lag, TracedClient};
 
 // Generates the contract bindings for the EigenlayerBeaconOracle contract.
 abigen!(
@@ -18,14 +32,109 @@ abigen!(
 // Maximum number of blocks to search backwards for (1 day of blocks).
 const MAX_DISTANCE_TO_FILL: u64 = 7200;
 
+// Maximum number of interval checkpoints submitted in a single tick, so a large gap is drained
+// over several ticks rather than flooding the mempool at once.
+const BACKFILL_BATCH_LIMIT: usize = 32;
+
+// Convenience alias for the fully-stacked middleware the keeper drives the contract through: a
+// nonce manager over the signer so concurrent/backfilled sends get correct sequential nonces.
+type OracleClient =
+    NonceManagerMiddleware<SignerMiddleware<Provider<TracedClient>, KeeperSigner>>;
+
+/// Stores the beacon root for a single interval checkpoint if it is not already in the contract.
+///
+/// Returns `Ok(true)` when a write was submitted (whether or not a receipt arrived before the
+/// resubmissions ran out), `Ok(false)` when the block was
+/// already present or was skipped because its consensus-layer root is absent or disagrees.
+async fn process_checkpoint(
+    client: &Arc<OracleClient>,
+    contract: &EigenlayerBeaconOracle<OracleClient>,
+    consensus: &ConsensusLayer,
+    oracle_address: Address,
+    interval_block_nb: u64,
+) -> Result<bool> {
+    // The node can return `None` for a block near the head during transient lag or a reorg;
+    // skip this checkpoint for the tick rather than panicking (which would kill the task).
+    let interval_block = match client.get_block(interval_block_nb).await? {
+        Some(block) => block,
+        None => {
+            tracing::warn!(interval_block_nb, "block not yet available, skipping this tick");
+            return Ok(false);
+        }
+    };
+    let interval_block_timestamp = interval_block.timestamp;
+    let interval_beacon_block_root = contract
+        .timestamp_to_block_root(interval_block_timestamp)
+        .call()
+        .await?;
+
+    // Already stored.
+    if interval_beacon_block_root != [0; 32] {
+        return Ok(false);
+    }
+
+    // Cross-verify against the consensus layer before writing. EIP-4788 exposes the execution
+    // block's `parent_beacon_block_root`; confirm it matches the CL's canonical root for the
+    // slot, otherwise the 4788 root is absent or wrong.
+    let parent_beacon_block_root = match interval_block.parent_beacon_block_root {
+        Some(root) => root,
+        None => {
+            tracing::warn!(
+                interval_block_nb,
+                "block has no parent_beacon_block_root (pre-4788?), skipping"
+            );
+            return Ok(false);
+        }
+    };
+    if !consensus
+        .verify_block_root(interval_block_timestamp, parent_beacon_block_root)
+        .await?
+    {
+        tracing::warn!(
+            interval_block_nb,
+            "consensus layer root is missing or disagrees, skipping write"
+        );
+        return Ok(false);
+    }
+
+    // Take the next nonce from the nonce manager so sequential backfilled sends don't collide,
+    // then submit as EIP-1559 with fee estimation and same-nonce resubmission so an underpriced
+    // tx can't stall the loop indefinitely.
+    let nonce = client.next();
+    let tx: Option<TransactionReceipt> = send_add_timestamp(
+        client.clone(),
+        oracle_address,
+        interval_block_timestamp,
+        nonce,
+        GasConfig::default(),
+    )
+    .await?;
+
+    if let Some(tx) = tx {
+        tracing::info!(
+            interval_block_nb,
+            tx_hash = ?tx.transaction_hash,
+            "added block to the contract"
+        );
+    }
+    Ok(true)
+}
+
 /// Asynchronously gets the latest block in the contract.
-async fn get_latest_block_in_contract(
-    rpc_url: String,
+///
+/// Takes the already-built, traced client so its `get_block_number`/`get_logs` calls are
+/// instrumented by the observability layer like every other RPC call in the tick.
+async fn get_latest_block_in_contract<M: Middleware>(
+    client: &M,
     oracle_address_bytes: Address,
-) -> Result<u64> {
-    let provider =
-        Provider::<Http>::try_from(rpc_url.clone()).expect("could not connect to client");
-    let latest_block = provider.get_block_number().await?;
+) -> Result<u64>
+where
+    M::Error: 'static,
+{
+    let latest_block = client
+        .get_block_number()
+        .await
+        .map_err(|e| anyhow::anyhow!(e.to_string()))?;
 
     let mut curr_block = latest_block;
     while curr_block > curr_block - MAX_DISTANCE_TO_FILL {
@@ -37,7 +146,10 @@ async fn get_latest_block_in_contract(
             .address(vec![oracle_address_bytes])
             .event("EigenLayerBeaconOracleUpdate(uint256,uint256,bytes32)");
 
-        let logs = provider.get_logs(&filter).await?;
+        let logs = client
+            .get_logs(&filter)
+            .await
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
         // Return the most recent block number from the logs (if any).
         if logs.len() > 0 {
             return Ok(logs[0].block_number.unwrap().as_u64());
@@ -51,90 +163,140 @@ async fn get_latest_block_in_contract(
     ))
 }
 
-async fn create_aws_signer() -> AWSSigner {
-    let access_key = std::env::var("ACCESS_KEY").expect("ACCESS_KEY must be in environment");
-    let secret_access_key =
-        std::env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY must be in environment");
-    let key_id: String = std::env::var("KEY_ID").expect("KEY_ID must be in environment");
-    let region = std::env::var("REGION").expect("REGION must be in environment");
-    let chain_id = std::env::var("CHAIN_ID").expect("CHAIN_ID must be in environment");
-    let chain_id = u64::from_str(&chain_id).expect("CHAIN_ID must be a number");
-    let aws_signer = AWSSigner::new(chain_id, access_key, secret_access_key, key_id, region)
-        .await
-        .expect("Cannot create AWS signer");
-    aws_signer
-}
+/// Runs the keeper's update loop for a single chain until the process is stopped. Spawned once per
+/// configured chain so all chains are maintained concurrently from one process.
+async fn run_oracle_for_chain(config: ChainConfig) -> Result<()> {
+    let oracle_address_bytes: [u8; 20] = hex::decode(&config.contract_address)
+        .expect("contract_address must be valid hex")
+        .try_into()
+        .expect("contract_address must be 20 bytes");
+    let oracle_address = Address::from(oracle_address_bytes);
 
-/// The main function that runs the application.
-#[tokio::main]
-async fn main() -> Result<(), anyhow::Error> {
-    dotenv::dotenv().ok();
+    let consensus = ConsensusLayer::new(&config.beacon_rpc_url).await?;
+    // Drive scheduling off the beacon slot clock so writes align with when fresh beacon roots
+    // actually become available, rather than a blind 60-second timer.
+    let slot_clock = consensus.slot_clock();
 
-    let block_interval = env::var("BLOCK_INTERVAL")?;
-    let block_interval = u64::from_str(&block_interval)?;
+    loop {
+        // A transient RPC failure should cost at most one tick, not the whole chain's task, so we
+        // run the tick fallibly and log-and-continue on error rather than propagating out of the
+        // spawned task (which would stop maintaining this chain with no restart).
+        if let Err(err) = run_tick(&config, oracle_address, oracle_address_bytes, &consensus).await
+        {
+            tracing::warn!(chain = %config.name, error = %err, "tick failed, retrying next slot");
+        }
 
-    let rpc_url = env::var("RPC_URL")?;
+        // Wake precisely at the next slot boundary, when the next beacon root becomes available.
+        tracing::debug!(
+            current_slot = slot_clock.current_slot(),
+            current_epoch = slot_clock.current_epoch(),
+            "sleeping until next slot"
+        );
+        tokio::time::sleep(slot_clock.duration_to_next_slot()).await;
+    }
+}
 
-    let contract_address = env::var("CONTRACT_ADDRESS")?;
-    let oracle_address_bytes: [u8; 20] = hex::decode(contract_address).unwrap().try_into().unwrap();
+/// Performs a single update tick for one chain: rebuilds the client, reads the contract's current
+/// block and the chain head, and backfills every missing interval checkpoint (up to the batch
+/// limit). Any error is returned so the caller can log it and retry on the next slot.
+async fn run_tick(
+    config: &ChainConfig,
+    oracle_address: Address,
+    oracle_address_bytes: [u8; 20],
+    consensus: &ConsensusLayer,
+) -> Result<()> {
+    // Wrap the HTTP transport so every JSON-RPC call is traced and metered.
+    let http = Http::from_str(&config.rpc_url).expect("could not connect to client");
+    let provider = Provider::new(TracedClient::new(
+        http,
+        config.name.clone(),
+        format!("{:?}", oracle_address),
+    ));
 
-    loop {
-        // Replace with your Ethereum node's HTTP endpoint
-        let provider =
-            Provider::<Http>::try_from(rpc_url.clone()).expect("could not connect to client");
+    let chain_id = provider.get_chainid().await?.as_u64();
+    let signer = build_signer(&config.signer, chain_id).await?;
+    let signer_address = signer.address();
 
-        let signer = create_aws_signer().await;
+    // Stack a nonce manager on top of the signer so concurrent/backfilled sends are assigned
+    // correct sequential nonces rather than all colliding on the latest on-chain nonce.
+    let client = Arc::new(NonceManagerMiddleware::new(
+        SignerMiddleware::new(provider, signer),
+        signer_address,
+    ));
+    // Seed the nonce manager from the pending on-chain count; `next()` increments from there, so
+    // each checkpoint's send gets the correct sequential nonce rather than starting from zero.
+    client
+        .initialize_nonce(Some(BlockNumber::Pending.into()))
+        .await?;
 
-        let client = Arc::new(SignerMiddleware::new(provider, signer));
+    let contract = EigenlayerBeaconOracle::new(oracle_address_bytes, client.clone());
 
-        let contract = EigenlayerBeaconOracle::new(oracle_address_bytes, client.clone());
+    let contract_curr_block =
+        get_latest_block_in_contract(client.as_ref(), oracle_address).await?;
 
-        let contract_curr_block =
-            get_latest_block_in_contract(rpc_url.clone(), Address::from(oracle_address_bytes))
-                .await
-                .unwrap();
+    // Read the current chain head; the backfill below runs up to a few blocks behind it.
+    let latest_block = client.get_block_number().await?;
 
-        // Check if latest_block + block_interval is less than the current block number.
-        let latest_block = client.get_block_number().await?;
+    record_block_lag(&config.name, contract_curr_block, latest_block.as_u64());
+    tracing::info!(
+        contract_curr_block,
+        latest_block = latest_block.as_u64(),
+        lag = latest_block.as_u64().saturating_sub(contract_curr_block),
+        "oracle status"
+    );
 
-        println!(
-            "The contract's current latest update is from block: {} and Goerli's latest block is: {}. Difference: {}",
-            contract_curr_block, latest_block, latest_block - contract_curr_block
+    // Backfill every missing interval checkpoint between the contract's last stored block and
+    // the current head, so the keeper self-heals after downtime instead of trailing forever.
+    // We stay a few blocks behind the head to avoid RPC reorg/stability issues.
+    let head = latest_block.as_u64().saturating_sub(5);
+    let mut checkpoint = contract_curr_block + config.block_interval;
+    let mut submitted = 0usize;
+    while checkpoint <= head && submitted < BACKFILL_BATCH_LIMIT {
+        tracing::info!(checkpoint, "processing interval checkpoint");
+        if process_checkpoint(&client, &contract, consensus, oracle_address, checkpoint).await? {
+            submitted += 1;
+        }
+        checkpoint += config.block_interval;
+    }
+    if checkpoint <= head {
+        tracing::info!(
+            remaining = head.saturating_sub(checkpoint) / config.block_interval + 1,
+            "backfill batch limit reached, continuing next tick"
         );
+    }
+    Ok(())
+}
 
-        // To avoid RPC stability issues, we use a block number 5 blocks behind the current block.
-        if contract_curr_block + block_interval < latest_block.as_u64() - 5 {
-            println!(
-                "Attempting to add timestamp of block {} to contract",
-                contract_curr_block + block_interval
-            );
-            let interval_block_nb = contract_curr_block + block_interval;
-
-            // Check if interval_block_nb is stored in the contract.
-            let interval_block = client.get_block(interval_block_nb).await?;
-            let interval_block_timestamp = interval_block.unwrap().timestamp;
-            let interval_beacon_block_root = contract
-                .timestamp_to_block_root(interval_block_timestamp)
-                .call()
-                .await?;
-
-            // If the interval block is not in the contract, store it.
-            if interval_beacon_block_root == [0; 32] {
-                let tx: Option<TransactionReceipt> = contract
-                    .add_timestamp(interval_block_timestamp)
-                    .send()
-                    .await?
-                    .await?;
-
-                if let Some(tx) = tx {
-                    println!(
-                        "Added block {:?} to the contract! Transaction: {:?}",
-                        interval_block_nb, tx.transaction_hash
-                    );
-                }
+/// The main function that runs the application: loads the multi-chain config and spawns one
+/// independent update loop per chain.
+#[tokio::main]
+async fn main() -> Result<(), anyhow::Error> {
+    dotenv::dotenv().ok();
+
+    // Structured logs to stdout and a Prometheus scrape endpoint for metrics.
+    tracing_subscriber::fmt()
+        .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
+        .init();
+    metrics_exporter_prometheus::PrometheusBuilder::new()
+        .install()
+        .expect("failed to install Prometheus exporter");
+
+    let cli = Cli::parse();
+    let config = KeeperConfig::load(&cli.config)?;
+
+    let mut handles = Vec::with_capacity(config.chains.len());
+    for chain in config.chains {
+        let name = chain.name.clone();
+        tracing::info!(chain = %name, "starting oracle task");
+        handles.push(tokio::spawn(async move {
+            if let Err(err) = run_oracle_for_chain(chain).await {
+                tracing::error!(chain = %name, error = %err, "oracle task exited");
             }
-        }
-        // Sleep for 1 minute.
-        let _ = tokio::time::sleep(tokio::time::Duration::from_secs((60) as u64)).await;
+        }));
+    }
+
+    for handle in handles {
+        let _ = handle.await;
     }
+    Ok(())
 }
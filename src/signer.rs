@@ -0,0 +1,134 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use ethers::{
+    signers::{LocalWallet, Signer, WalletError},
+    types::{
+        transaction::{eip2718::TypedTransaction, eip712::Eip712},
+        Address, Signature,
+    },
+};
+use ethers_aws::aws_signer::AWSSigner;
+use serde::Deserialize;
+
+/// Key-management backend, selected per chain by config. This lets contributors run the keeper
+/// locally with a raw key or a JSON keystore while production keeps AWS KMS, without code edits.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(tag = "backend", rename_all = "kebab-case")]
+pub enum SignerBackend {
+    /// AWS KMS, configured through the usual `ACCESS_KEY`/`SECRET_ACCESS_KEY`/`KEY_ID`/`REGION`
+    /// environment variables.
+    AwsKms,
+    /// A raw hex-encoded secp256k1 private key, intended for local/dev runs.
+    LocalPrivateKey { private_key: String },
+    /// An encrypted JSON keystore file unlocked with `password`.
+    KeystoreJson { path: PathBuf, password: String },
+}
+
+/// A [`Signer`] that dispatches to whichever backend the config selected, so the rest of the
+/// stack can treat every backend uniformly when building the `SignerMiddleware`.
+#[derive(Debug, Clone)]
+pub enum KeeperSigner {
+    Aws(AWSSigner),
+    Local(LocalWallet),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum KeeperSignerError {
+    #[error(transparent)]
+    Local(#[from] WalletError),
+    #[error("aws signer error: {0}")]
+    Aws(String),
+}
+
+/// Constructs the configured signer, bound to `chain_id`.
+pub async fn build_signer(backend: &SignerBackend, chain_id: u64) -> Result<KeeperSigner> {
+    match backend {
+        SignerBackend::AwsKms => Ok(KeeperSigner::Aws(create_aws_signer(chain_id).await?)),
+        SignerBackend::LocalPrivateKey { private_key } => {
+            let wallet: LocalWallet = private_key.parse()?;
+            Ok(KeeperSigner::Local(wallet.with_chain_id(chain_id)))
+        }
+        SignerBackend::KeystoreJson { path, password } => {
+            let wallet = LocalWallet::decrypt_keystore(path, password)?;
+            Ok(KeeperSigner::Local(wallet.with_chain_id(chain_id)))
+        }
+    }
+}
+
+async fn create_aws_signer(chain_id: u64) -> Result<AWSSigner> {
+    let access_key = env_var("ACCESS_KEY")?;
+    let secret_access_key = env_var("SECRET_ACCESS_KEY")?;
+    let key_id = env_var("KEY_ID")?;
+    let region = env_var("REGION")?;
+    AWSSigner::new(chain_id, access_key, secret_access_key, key_id, region)
+        .await
+        .map_err(|e| anyhow::anyhow!("cannot create AWS signer: {e}"))
+}
+
+fn env_var(name: &str) -> Result<String> {
+    std::env::var(name).map_err(|_| anyhow::anyhow!("{name} must be set for the aws-kms backend"))
+}
+
+#[async_trait]
+impl Signer for KeeperSigner {
+    type Error = KeeperSignerError;
+
+    async fn sign_message<S: Send + Sync + AsRef<[u8]>>(
+        &self,
+        message: S,
+    ) -> Result<Signature, Self::Error> {
+        match self {
+            KeeperSigner::Aws(s) => s
+                .sign_message(message)
+                .await
+                .map_err(|e| KeeperSignerError::Aws(e.to_string())),
+            KeeperSigner::Local(s) => Ok(s.sign_message(message).await?),
+        }
+    }
+
+    async fn sign_transaction(&self, tx: &TypedTransaction) -> Result<Signature, Self::Error> {
+        match self {
+            KeeperSigner::Aws(s) => s
+                .sign_transaction(tx)
+                .await
+                .map_err(|e| KeeperSignerError::Aws(e.to_string())),
+            KeeperSigner::Local(s) => Ok(s.sign_transaction(tx).await?),
+        }
+    }
+
+    async fn sign_typed_data<T: Eip712 + Send + Sync>(
+        &self,
+        payload: &T,
+    ) -> Result<Signature, Self::Error> {
+        match self {
+            KeeperSigner::Aws(s) => s
+                .sign_typed_data(payload)
+                .await
+                .map_err(|e| KeeperSignerError::Aws(e.to_string())),
+            KeeperSigner::Local(s) => Ok(s.sign_typed_data(payload).await?),
+        }
+    }
+
+    fn address(&self) -> Address {
+        match self {
+            KeeperSigner::Aws(s) => s.address(),
+            KeeperSigner::Local(s) => s.address(),
+        }
+    }
+
+    fn chain_id(&self) -> u64 {
+        match self {
+            KeeperSigner::Aws(s) => s.chain_id(),
+            KeeperSigner::Local(s) => s.chain_id(),
+        }
+    }
+
+    fn with_chain_id<T: Into<u64>>(self, chain_id: T) -> Self {
+        match self {
+            KeeperSigner::Aws(s) => KeeperSigner::Aws(s.with_chain_id(chain_id)),
+            KeeperSigner::Local(s) => KeeperSigner::Local(s.with_chain_id(chain_id)),
+        }
+    }
+}
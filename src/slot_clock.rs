@@ -0,0 +1,77 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use ethers::types::U256;
+
+// Default slot time, used only as a fallback when the beacon node's `config/spec` cannot be
+// read. The live value is sourced per-chain from the spec (see `ConsensusLayer::new`) so the
+// clock is correct on testnets/L2s where it differs.
+pub const SECONDS_PER_SLOT: u64 = 12;
+pub const SLOTS_PER_EPOCH: u64 = 32;
+
+/// Maps wall-clock and execution-layer timestamps onto beacon slots and epochs, anchored to the
+/// chain's genesis time. Shared between the scheduler (to wake on slot boundaries) and the
+/// consensus-layer verifier (to derive the slot for a given block timestamp), so both use the
+/// same genesis/slot math.
+///
+/// The clock drives *scheduling* only — it decides when a tick runs, aligned to slot boundaries
+/// so writes happen once a fresh beacon root is available. The checkpoint interval itself stays
+/// block-denominated (see [`crate::config::ChainConfig::block_interval`]), since the oracle is
+/// keyed by execution-block timestamp.
+#[derive(Clone, Copy)]
+pub struct SlotClock {
+    genesis_time: u64,
+    seconds_per_slot: u64,
+}
+
+impl SlotClock {
+    pub fn new(genesis_time: u64, seconds_per_slot: u64) -> Self {
+        Self {
+            genesis_time,
+            seconds_per_slot,
+        }
+    }
+
+    /// The unix genesis timestamp of the beacon chain.
+    pub fn genesis_time(&self) -> u64 {
+        self.genesis_time
+    }
+
+    /// The slot containing the given unix `timestamp`. Timestamps before genesis map to slot 0.
+    pub fn slot_of_timestamp(&self, timestamp: u64) -> u64 {
+        timestamp.saturating_sub(self.genesis_time) / self.seconds_per_slot
+    }
+
+    /// Convenience wrapper for execution-layer block timestamps, which ethers exposes as [`U256`].
+    pub fn slot_of_block_timestamp(&self, timestamp: U256) -> u64 {
+        self.slot_of_timestamp(timestamp.as_u64())
+    }
+
+    /// The unix timestamp at which `slot` begins.
+    pub fn start_of_slot(&self, slot: u64) -> u64 {
+        self.genesis_time + slot * self.seconds_per_slot
+    }
+
+    /// The current slot according to the local clock.
+    pub fn current_slot(&self) -> u64 {
+        self.slot_of_timestamp(now())
+    }
+
+    /// The current epoch according to the local clock.
+    pub fn current_epoch(&self) -> u64 {
+        self.current_slot() / SLOTS_PER_EPOCH
+    }
+
+    /// The time remaining until the next slot boundary, used to wake the update loop precisely
+    /// at the next actionable slot instead of polling on a blind 60-second timer.
+    pub fn duration_to_next_slot(&self) -> Duration {
+        let next_slot_start = self.start_of_slot(self.current_slot() + 1);
+        Duration::from_secs(next_slot_start.saturating_sub(now()))
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system clock is before unix epoch")
+        .as_secs()
+}
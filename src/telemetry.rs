@@ -0,0 +1,79 @@
+use std::fmt::Debug;
+
+use async_trait::async_trait;
+use ethers::providers::{Http, JsonRpcClient, ProviderError};
+use serde::{de::DeserializeOwned, Serialize};
+use tracing::Instrument;
+
+/// A [`JsonRpcClient`] decorator that instruments every JSON-RPC call with a `tracing` span and
+/// Prometheus metrics: request/error counters, a latency histogram, all labelled by RPC method
+/// and the chain/contract the keeper is servicing.
+///
+/// It is transparent to the rest of the stack — `Provider<TracedClient>` plugs in wherever
+/// `Provider<Http>` was used before, so `SignerMiddleware` and the contract bindings are
+/// unchanged.
+#[derive(Debug)]
+pub struct TracedClient {
+    inner: Http,
+    chain: String,
+    contract: String,
+}
+
+impl TracedClient {
+    pub fn new(inner: Http, chain: impl Into<String>, contract: impl Into<String>) -> Self {
+        Self {
+            inner,
+            chain: chain.into(),
+            contract: contract.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl JsonRpcClient for TracedClient {
+    type Error = ProviderError;
+
+    async fn request<T, R>(&self, method: &str, params: T) -> Result<R, Self::Error>
+    where
+        T: Debug + Serialize + Send + Sync,
+        R: DeserializeOwned + Send,
+    {
+        let span = tracing::info_span!(
+            "rpc_call",
+            method = method,
+            chain = %self.chain,
+            contract = %self.contract,
+        );
+        let labels = [
+            ("method", method.to_string()),
+            ("chain", self.chain.clone()),
+            ("contract", self.contract.clone()),
+        ];
+
+        let started = std::time::Instant::now();
+        let result = self.inner.request(method, params).instrument(span).await;
+        let latency = started.elapsed().as_secs_f64();
+
+        metrics::counter!("rpc_requests_total", &labels).increment(1);
+        metrics::histogram!("rpc_request_duration_seconds", &labels).record(latency);
+
+        match &result {
+            Ok(_) => tracing::debug!(method, latency, "rpc call succeeded"),
+            Err(err) => {
+                metrics::counter!("rpc_errors_total", &labels).increment(1);
+                tracing::warn!(method, latency, error = %err, "rpc call failed");
+            }
+        }
+
+        result
+    }
+}
+
+/// Records how far the oracle is behind the chain head. Called once per loop iteration so that
+/// operators can alert on a growing gap.
+pub fn record_block_lag(chain: &str, contract_curr_block: u64, latest_block: u64) {
+    let labels = [("chain", chain.to_string())];
+    metrics::gauge!("contract_curr_block", &labels).set(contract_curr_block as f64);
+    metrics::gauge!("latest_block", &labels).set(latest_block as f64);
+    metrics::gauge!("block_lag", &labels).set(latest_block.saturating_sub(contract_curr_block) as f64);
+}